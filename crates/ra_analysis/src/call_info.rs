@@ -3,8 +3,8 @@ use std::cmp::{max, min};
 use ra_db::{SyntaxDatabase, Cancelable};
 use ra_syntax::{
     AstNode, SyntaxNode, TextUnit, TextRange,
-    SyntaxKind::FN_DEF,
-    ast::{self, ArgListOwner, DocCommentsOwner},
+    SyntaxKind::{FN_DEF, STRUCT_DEF, ENUM_VARIANT, MACRO_CALL},
+    ast::{self, ArgListOwner, DocCommentsOwner, NameOwner, StructFlavor},
 };
 use ra_editor::find_node_at_offset;
 
@@ -36,59 +36,109 @@ fn signature_and_active_param(
     // Resolve the function's NameRef (NOTE: this isn't entirely accurate).
     let file_symbols = db.index_resolve(name_ref)?;
     for symbol in file_symbols {
-        if symbol.ptr.kind() == FN_DEF {
-            let fn_file = db.source_file(symbol.file_id);
-            let fn_def = symbol.ptr.resolve(&fn_file);
-            let fn_def = ast::FnDef::cast(&fn_def).unwrap();
-            if let Some(descriptor) = FnSignatureInfo::new(fn_def) {
-                // If we have a calling expression let's find which argument we are on
-                let mut current_parameter = None;
-
-                let num_params = descriptor.params.len();
+        let (descriptor, has_self) = match symbol.ptr.kind() {
+            FN_DEF => {
+                let fn_file = db.source_file(symbol.file_id);
+                let fn_def = symbol.ptr.resolve(&fn_file);
+                let fn_def = ast::FnDef::cast(&fn_def).unwrap();
                 let has_self = fn_def.param_list().and_then(|l| l.self_param()).is_some();
-
-                if num_params == 1 {
-                    if !has_self {
-                        current_parameter = Some(0);
-                    }
-                } else if num_params > 1 {
-                    // Count how many parameters into the call we are.
-                    // TODO: This is best effort for now and should be fixed at some point.
-                    // It may be better to see where we are in the arg_list and then check
-                    // where offset is in that list (or beyond).
-                    // Revisit this after we get documentation comments in.
-                    if let Some(ref arg_list) = calling_node.arg_list() {
-                        let start = arg_list.syntax().range().start();
-
-                        let range_search = TextRange::from_to(start, position.offset);
-                        let mut commas: usize = arg_list
-                            .syntax()
-                            .text()
-                            .slice(range_search)
-                            .to_string()
-                            .matches(',')
-                            .count();
-
-                        // If we have a method call eat the first param since it's just self.
-                        if has_self {
-                            commas += 1;
-                        }
-
-                        current_parameter = Some(commas);
-                    }
-                }
-
-                return Ok(Some((descriptor, current_parameter)));
+                (FnSignatureInfo::new(fn_def), has_self)
+            }
+            STRUCT_DEF => {
+                let struct_file = db.source_file(symbol.file_id);
+                let struct_def = symbol.ptr.resolve(&struct_file);
+                let struct_def = ast::StructDef::cast(&struct_def).unwrap();
+                (FnSignatureInfo::for_struct(struct_def), false)
             }
+            ENUM_VARIANT => {
+                let variant_file = db.source_file(symbol.file_id);
+                let variant = symbol.ptr.resolve(&variant_file);
+                let variant = ast::EnumVariant::cast(&variant).unwrap();
+                (FnSignatureInfo::for_enum_variant(variant), false)
+            }
+            MACRO_CALL => {
+                let macro_file = db.source_file(symbol.file_id);
+                let macro_call = symbol.ptr.resolve(&macro_file);
+                let macro_call = ast::MacroCall::cast(&macro_call).unwrap();
+                (FnSignatureInfo::for_macro(macro_call), false)
+            }
+            _ => continue,
+        };
+
+        if let Some(descriptor) = descriptor {
+            let current_parameter =
+                active_parameter(&descriptor, has_self, calling_node.arg_list(), position.offset);
+            return Ok(Some((descriptor, current_parameter)));
         }
     }
 
     Ok(None)
 }
 
+/// Figures out which parameter of `descriptor` the cursor is currently on,
+/// if any.
+fn active_parameter(
+    descriptor: &FnSignatureInfo,
+    has_self: bool,
+    arg_list: Option<&ast::ArgList>,
+    offset: TextUnit,
+) -> Option<usize> {
+    let num_params = descriptor.params.len();
+    let num_args_params = if has_self { num_params - 1 } else { num_params };
+    if num_args_params == 0 {
+        return None;
+    }
+
+    let arg_list = arg_list?;
+    // Clamp to the last real parameter: a trailing comma or extra argument
+    // would otherwise push this one past the end of `descriptor.params`.
+    let mut current_parameter = active_param_index(arg_list, offset).min(num_args_params - 1);
+
+    // If we have a method call eat the first param since it's just self.
+    if has_self {
+        current_parameter += 1;
+    }
+
+    Some(current_parameter)
+}
+
+/// Finds the index of the argument that `offset` is located in, by walking
+/// the direct argument expressions of `arg_list` rather than counting commas
+/// in the source text (which gets confused by commas inside nested calls,
+/// closures, literals, etc). If `offset` is past the last argument (e.g. on a
+/// trailing comma or in trailing whitespace), the index of the next, not yet
+/// written, argument is returned.
+fn active_param_index(arg_list: &ast::ArgList, offset: TextUnit) -> usize {
+    arg_list
+        .args()
+        .position(|arg| offset <= arg.syntax().range().end())
+        .unwrap_or_else(|| arg_list.args().count())
+}
+
+/// Finds the index (into `text`) of the `)` matching the `(` at `open_idx`.
+/// `open_idx` is a byte offset (as returned by `str::find`), so we skip by
+/// byte index rather than by character count.
+fn find_matching_paren(text: &str, open_idx: usize) -> Option<usize> {
+    let mut depth = 0i32;
+    for (i, c) in text.char_indices().skip_while(|&(i, _)| i < open_idx) {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
 enum FnCallNode<'a> {
     CallExpr(&'a ast::CallExpr),
     MethodCallExpr(&'a ast::MethodCallExpr),
+    MacroCall(&'a ast::MacroCall),
 }
 
 impl<'a> FnCallNode<'a> {
@@ -99,6 +149,9 @@ impl<'a> FnCallNode<'a> {
         if let Some(expr) = find_node_at_offset::<ast::MethodCallExpr>(syntax, offset) {
             return Some(FnCallNode::MethodCallExpr(expr));
         }
+        if let Some(expr) = find_node_at_offset::<ast::MacroCall>(syntax, offset) {
+            return Some(FnCallNode::MacroCall(expr));
+        }
         None
     }
 
@@ -114,6 +167,8 @@ impl<'a> FnCallNode<'a> {
                 .children()
                 .filter_map(ast::NameRef::cast)
                 .nth(0),
+
+            FnCallNode::MacroCall(macro_call) => macro_call.path()?.segment()?.name_ref(),
         }
     }
 
@@ -121,14 +176,29 @@ impl<'a> FnCallNode<'a> {
         match *self {
             FnCallNode::CallExpr(expr) => expr.arg_list(),
             FnCallNode::MethodCallExpr(expr) => expr.arg_list(),
+            // Macro arguments are a token tree, not a typed `ArgList`, so we
+            // can't point at a specific active parameter.
+            FnCallNode::MacroCall(_) => None,
         }
     }
 }
 
+/// A single parameter of a `FnSignatureInfo`, carrying both its rendered
+/// text (`name: Type` for a function parameter, the bare type for a tuple
+/// constructor field, `$name:fragment` for a macro capture) and its byte
+/// range within `FnSignatureInfo::label`, so
+/// that a client can bold the active parameter inside the rendered
+/// signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ParamInfo {
+    label: String,
+    range: TextRange,
+}
+
 #[derive(Debug, Clone)]
 struct FnSignatureInfo {
     label: String,
-    params: Vec<String>,
+    params: Vec<ParamInfo>,
     doc: Option<String>,
 }
 
@@ -160,38 +230,145 @@ impl FnSignatureInfo {
             // Remove the comment from the label
             label.replace_range(start..end, "");
 
-            // Massage markdown
-            let mut processed_lines = Vec::new();
-            let mut in_code_block = false;
-            for line in docs.lines() {
-                if line.starts_with("```") {
-                    in_code_block = !in_code_block;
-                }
+            doc = FnSignatureInfo::process_doc_comments(&docs);
+        }
 
-                let line = if in_code_block && line.starts_with("```") && !line.contains("rust") {
-                    "```rust".into()
-                } else {
-                    line.to_string()
-                };
+        let label = label.trim().to_owned();
+        let params = FnSignatureInfo::param_list(node, &label);
+
+        Some(FnSignatureInfo { params, label, doc })
+    }
+
+    /// Builds pseudo call info for a tuple struct, so that e.g. `Point(<|>)`
+    /// shows the types of `Point`'s positional fields. Returns `None` for
+    /// record and unit structs, which aren't called like functions.
+    fn for_struct(node: &ast::StructDef) -> Option<Self> {
+        let name = node.name()?.text().to_string();
+        let doc = FnSignatureInfo::extract_doc_comments(node)
+            .and_then(|(_, docs)| FnSignatureInfo::process_doc_comments(&docs));
+        FnSignatureInfo::for_tuple_constructor(name, node.flavor(), doc)
+    }
+
+    /// Builds pseudo call info for a tuple enum variant, so that e.g.
+    /// `Some(<|>)` or `MyEnum::Variant(<|>)` show the types of the variant's
+    /// positional fields. Returns `None` for record and unit variants.
+    fn for_enum_variant(node: &ast::EnumVariant) -> Option<Self> {
+        let name = node.name()?.text().to_string();
+        let doc = FnSignatureInfo::extract_doc_comments(node)
+            .and_then(|(_, docs)| FnSignatureInfo::process_doc_comments(&docs));
+        FnSignatureInfo::for_tuple_constructor(name, node.flavor(), doc)
+    }
+
+    /// Shared by `for_struct` and `for_enum_variant`: builds the pseudo call
+    /// info for a tuple constructor `name(Type, Type, ...)` from its already
+    /// extracted name, field flavor and doc comment. Returns `None` for
+    /// record and unit flavors.
+    fn for_tuple_constructor(name: String, flavor: StructFlavor, doc: Option<String>) -> Option<Self> {
+        let field_types = FnSignatureInfo::tuple_field_types(flavor)?;
+
+        let label = format!("{}({})", name, field_types.join(", "));
+        let params = FnSignatureInfo::params_from_labels(&label, field_types);
+
+        Some(FnSignatureInfo { label, params, doc })
+    }
+
+    /// Builds pseudo call info for a `macro_rules!` definition, so that
+    /// `println!(<|>)` or a call to a user's macro surfaces at least its
+    /// documentation and a label. The parameters are the `$name:fragment`
+    /// captures of the first parenthesised matcher only (see
+    /// `macro_matcher_params`); other arms are ignored, and a definition
+    /// with no such captures yields a signature with no parameters.
+    fn for_macro(node: &ast::MacroCall) -> Option<Self> {
+        let name = FnSignatureInfo::macro_name(node)?;
+        let doc = FnSignatureInfo::extract_doc_comments(node)
+            .and_then(|(_, docs)| FnSignatureInfo::process_doc_comments(&docs));
+
+        let field_labels = FnSignatureInfo::macro_matcher_params(node).unwrap_or_default();
+        let label = format!("{}!({})", name, field_labels.join(", "));
+        let params = FnSignatureInfo::params_from_labels(&label, field_labels);
+
+        Some(FnSignatureInfo { label, params, doc })
+    }
 
-                processed_lines.push(line);
+    /// Returns the defined name of a macro, whether `node` is the
+    /// `macro_rules! name { ... }` definition itself (where `path()` is just
+    /// the literal `macro_rules` keyword and the real name is a sibling
+    /// `Name` token) or an invocation `name!(...)`.
+    fn macro_name(node: &ast::MacroCall) -> Option<String> {
+        let path_name = node.path()?.segment()?.name_ref()?.text();
+        if path_name == "macro_rules" {
+            node.syntax()
+                .children()
+                .filter_map(ast::Name::cast)
+                .next()
+                .map(|name| name.text().to_string())
+        } else {
+            Some(path_name.to_string())
+        }
+    }
+
+    /// Pulls `$name:fragment` captures out of the first `(`-delimited group
+    /// of a `macro_rules!` definition's token tree, e.g. `($x:expr, $y:expr)`
+    /// yields `["$x:expr", "$y:expr"]`. Earlier `{}`/`[]`-delimited arms are
+    /// skipped, and captures inside repetitions are listed flat, without the
+    /// repetition. Returns `None` if no fragment captures could be found.
+    fn macro_matcher_params(node: &ast::MacroCall) -> Option<Vec<String>> {
+        let body = node.token_tree()?.syntax().text().to_string();
+
+        let matcher_start = body.find('(')?;
+        let matcher_end = find_matching_paren(&body, matcher_start)?;
+        let matcher = &body[matcher_start + 1..matcher_end];
+
+        let mut params = vec![];
+        let mut rest = matcher;
+        while let Some(dollar) = rest.find('$') {
+            rest = &rest[dollar + 1..];
+            let name_len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or_else(|| rest.len());
+            let name = &rest[..name_len];
+            rest = &rest[name_len..];
+
+            if name.is_empty() || !rest.starts_with(':') {
+                continue;
             }
+            rest = &rest[1..];
+
+            let frag_len = rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .unwrap_or_else(|| rest.len());
+            let fragment = &rest[..frag_len];
+            rest = &rest[frag_len..];
 
-            if !processed_lines.is_empty() {
-                doc = Some(processed_lines.join("\n"));
+            if fragment.is_empty() {
+                continue;
             }
+            params.push(format!("${}:{}", name, fragment));
         }
 
-        let params = FnSignatureInfo::param_list(node);
+        if params.is_empty() {
+            None
+        } else {
+            Some(params)
+        }
+    }
 
-        Some(FnSignatureInfo {
-            params,
-            label: label.trim().to_owned(),
-            doc,
-        })
+    /// Extracts the field types of a tuple struct/variant flavor, in order.
+    /// Returns `None` for record and unit flavors.
+    fn tuple_field_types(flavor: StructFlavor) -> Option<Vec<String>> {
+        match flavor {
+            StructFlavor::Tuple(fields) => Some(
+                fields
+                    .fields()
+                    .filter_map(|f| f.type_ref())
+                    .map(|type_ref| type_ref.syntax().text().to_string())
+                    .collect(),
+            ),
+            StructFlavor::Named(_) | StructFlavor::Unit => None,
+        }
     }
 
-    fn extract_doc_comments(node: &ast::FnDef) -> Option<(TextRange, String)> {
+    fn extract_doc_comments<N: DocCommentsOwner>(node: &N) -> Option<(TextRange, String)> {
         if node.doc_comments().count() == 0 {
             return None;
         }
@@ -211,23 +388,76 @@ impl FnSignatureInfo {
         Some((range, comment_text))
     }
 
-    fn param_list(node: &ast::FnDef) -> Vec<String> {
-        let mut res = vec![];
+    /// Massages raw doc comment text into markdown suitable for display,
+    /// e.g. by tagging bare code fences as `rust`. Returns `None` if there's
+    /// nothing left to show.
+    fn process_doc_comments(docs: &str) -> Option<String> {
+        let mut processed_lines = Vec::new();
+        let mut in_code_block = false;
+        for line in docs.lines() {
+            if line.starts_with("```") {
+                in_code_block = !in_code_block;
+            }
+
+            let line = if in_code_block && line.starts_with("```") && !line.contains("rust") {
+                "```rust".into()
+            } else {
+                line.to_string()
+            };
+
+            processed_lines.push(line);
+        }
+
+        if processed_lines.is_empty() {
+            None
+        } else {
+            Some(processed_lines.join("\n"))
+        }
+    }
+
+    fn param_list(node: &ast::FnDef, label: &str) -> Vec<ParamInfo> {
+        let mut labels = vec![];
         if let Some(param_list) = node.param_list() {
             if let Some(self_param) = param_list.self_param() {
-                res.push(self_param.syntax().text().to_string())
+                labels.push(self_param.syntax().text().to_string())
             }
 
-            // Maybe use param.pat here? See if we can just extract the name?
-            //res.extend(param_list.params().map(|p| p.syntax().text().to_string()));
-            res.extend(
-                param_list
-                    .params()
-                    .filter_map(|p| p.pat())
-                    .map(|pat| pat.syntax().text().to_string()),
-            );
+            // Use the full param text (`name: Type`), not just the pattern,
+            // so clients can render the parameter's type.
+            labels.extend(param_list.params().map(|p| p.syntax().text().to_string()));
         }
-        res
+        FnSignatureInfo::params_from_labels(label, labels)
+    }
+
+    /// Pairs each parameter's rendered text with its byte range inside
+    /// `label`, by locating each label in turn starting just past the
+    /// previous match.
+    fn params_from_labels(label: &str, param_labels: Vec<String>) -> Vec<ParamInfo> {
+        let mut search_start = 0;
+        param_labels
+            .into_iter()
+            .map(|param_label| {
+                let range = match label[search_start..].find(param_label.as_str()) {
+                    Some(idx) => {
+                        let start = search_start + idx;
+                        let end = start + param_label.len();
+                        search_start = end;
+                        TextRange::from_to(
+                            TextUnit::from_usize(start),
+                            TextUnit::from_usize(end),
+                        )
+                    }
+                    None => TextRange::from_to(
+                        TextUnit::from_usize(search_start),
+                        TextUnit::from_usize(search_start),
+                    ),
+                };
+                ParamInfo {
+                    label: param_label,
+                    range,
+                }
+            })
+            .collect()
     }
 }
 
@@ -242,6 +472,10 @@ mod tests {
         analysis.call_info(position).unwrap().unwrap()
     }
 
+    fn param_labels(info: &CallInfo) -> Vec<String> {
+        info.parameters.iter().map(|p| p.label.clone()).collect()
+    }
+
     #[test]
     fn test_fn_signature_two_args_first() {
         let info = call_info(
@@ -249,7 +483,10 @@ mod tests {
 fn bar() { foo(<|>3, ); }"#,
         );
 
-        assert_eq!(info.parameters, vec!("x".to_string(), "y".to_string()));
+        assert_eq!(
+            param_labels(&info),
+            vec!("x: u32".to_string(), "y: u32".to_string())
+        );
         assert_eq!(info.active_parameter, Some(0));
     }
 
@@ -260,10 +497,24 @@ fn bar() { foo(<|>3, ); }"#,
 fn bar() { foo(3, <|>); }"#,
         );
 
-        assert_eq!(info.parameters, vec!("x".to_string(), "y".to_string()));
+        assert_eq!(
+            param_labels(&info),
+            vec!("x: u32".to_string(), "y: u32".to_string())
+        );
         assert_eq!(info.active_parameter, Some(1));
     }
 
+    #[test]
+    fn test_fn_signature_active_param_clamped_to_last_param() {
+        let info = call_info(
+            r#"fn foo(x: u32) -> u32 {x}
+fn bar() { foo(1, <|>); }"#,
+        );
+
+        assert_eq!(param_labels(&info), vec!["x: u32".to_string()]);
+        assert_eq!(info.active_parameter, Some(0));
+    }
+
     #[test]
     fn test_fn_signature_for_impl() {
         let info = call_info(
@@ -271,7 +522,7 @@ fn bar() { foo(3, <|>); }"#,
 fn bar() {let _ : F = F::new(<|>);}"#,
         );
 
-        assert_eq!(info.parameters, Vec::<String>::new());
+        assert_eq!(param_labels(&info), Vec::<String>::new());
         assert_eq!(info.active_parameter, None);
     }
 
@@ -293,7 +544,7 @@ fn bar() {
 }"#,
         );
 
-        assert_eq!(info.parameters, vec!["&self".to_string()]);
+        assert_eq!(param_labels(&info), vec!["&self".to_string()]);
         assert_eq!(info.active_parameter, None);
     }
 
@@ -315,7 +566,10 @@ fn bar() {
 }"#,
         );
 
-        assert_eq!(info.parameters, vec!["&self".to_string(), "x".to_string()]);
+        assert_eq!(
+            param_labels(&info),
+            vec!["&self".to_string(), "x: i32".to_string()]
+        );
         assert_eq!(info.active_parameter, Some(1));
     }
 
@@ -335,10 +589,14 @@ fn bar() {
 "#,
         );
 
-        assert_eq!(info.parameters, vec!["j".to_string()]);
+        assert_eq!(param_labels(&info), vec!["j: u32".to_string()]);
         assert_eq!(info.active_parameter, Some(0));
         assert_eq!(info.label, "fn foo(j: u32) -> u32".to_string());
         assert_eq!(info.doc, Some("test".into()));
+        assert_eq!(
+            info.parameters[0].range,
+            TextRange::from_to(TextUnit::from_usize(7), TextUnit::from_usize(13))
+        );
     }
 
     #[test]
@@ -363,7 +621,7 @@ pub fn do() {
 }"#,
         );
 
-        assert_eq!(info.parameters, vec!["x".to_string()]);
+        assert_eq!(param_labels(&info), vec!["x: i32".to_string()]);
         assert_eq!(info.active_parameter, Some(0));
         assert_eq!(info.label, "pub fn add_one(x: i32) -> i32".to_string());
         assert_eq!(
@@ -409,7 +667,7 @@ pub fn do_it() {
 }"#,
         );
 
-        assert_eq!(info.parameters, vec!["x".to_string()]);
+        assert_eq!(param_labels(&info), vec!["x: i32".to_string()]);
         assert_eq!(info.active_parameter, Some(0));
         assert_eq!(info.label, "pub fn add_one(x: i32) -> i32".to_string());
         assert_eq!(
@@ -463,8 +721,8 @@ pub fn foo() {
         );
 
         assert_eq!(
-            info.parameters,
-            vec!["&mut self".to_string(), "ctx".to_string()]
+            param_labels(&info),
+            vec!["&mut self".to_string(), "ctx: &mut Self::Context".to_string()]
         );
         assert_eq!(info.active_parameter, Some(1));
         assert_eq!(
@@ -478,4 +736,183 @@ By default this method stops actor's `Context`."#
         );
     }
 
+    #[test]
+    fn test_fn_signature_active_param_for_nested_call() {
+        let info = call_info(
+            r#"fn bar(x: i32, y: i32) -> i32 { x + y }
+fn foo(x: i32, y: i32) -> i32 {x + y}
+fn baz() { foo(bar(1, 2), <|>); }"#,
+        );
+
+        assert_eq!(
+            param_labels(&info),
+            vec!("x: i32".to_string(), "y: i32".to_string())
+        );
+        assert_eq!(info.active_parameter, Some(1));
+    }
+
+    #[test]
+    fn test_fn_signature_active_param_for_closure_arg() {
+        let info = call_info(
+            r#"fn foo(f: i32, g: i32) -> i32 {f + g}
+fn bar() { foo(|a, b| a + b, <|>); }"#,
+        );
+
+        assert_eq!(
+            param_labels(&info),
+            vec!("f: i32".to_string(), "g: i32".to_string())
+        );
+        assert_eq!(info.active_parameter, Some(1));
+    }
+
+    #[test]
+    fn test_fn_signature_active_param_for_string_literal_with_comma() {
+        let info = call_info(
+            r#"fn foo(x: &str, y: i32) -> i32 {y}
+fn bar() { foo("a,b", <|>); }"#,
+        );
+
+        assert_eq!(
+            param_labels(&info),
+            vec!("x: &str".to_string(), "y: i32".to_string())
+        );
+        assert_eq!(info.active_parameter, Some(1));
+    }
+
+    #[test]
+    fn test_fn_signature_for_tuple_struct() {
+        let info = call_info(
+            r#"struct Point(i32, i32);
+fn bar() { Point(<|>); }"#,
+        );
+
+        assert_eq!(
+            param_labels(&info),
+            vec!("i32".to_string(), "i32".to_string())
+        );
+        assert_eq!(info.active_parameter, Some(0));
+        assert_eq!(info.label, "Point(i32, i32)".to_string());
+    }
+
+    #[test]
+    fn test_fn_signature_for_tuple_struct_second_param() {
+        let info = call_info(
+            r#"struct Point(i32, i32);
+fn bar() { Point(1, <|>); }"#,
+        );
+
+        assert_eq!(
+            param_labels(&info),
+            vec!("i32".to_string(), "i32".to_string())
+        );
+        assert_eq!(info.active_parameter, Some(1));
+
+        // Each occurrence of the repeated "i32" text must get its own,
+        // non-overlapping range rather than both anchoring to the first
+        // match.
+        assert_eq!(
+            info.parameters[0].range,
+            TextRange::from_to(TextUnit::from_usize(6), TextUnit::from_usize(9))
+        );
+        assert_eq!(
+            info.parameters[1].range,
+            TextRange::from_to(TextUnit::from_usize(11), TextUnit::from_usize(14))
+        );
+    }
+
+    #[test]
+    fn test_fn_signature_for_enum_variant() {
+        let info = call_info(
+            r#"enum Option<T> { Some(T), None }
+fn bar() { Option::Some(<|>); }"#,
+        );
+
+        assert_eq!(param_labels(&info), vec!("T".to_string()));
+        assert_eq!(info.active_parameter, Some(0));
+        assert_eq!(info.label, "Some(T)".to_string());
+        assert_eq!(
+            info.parameters[0].range,
+            TextRange::from_to(TextUnit::from_usize(5), TextUnit::from_usize(6))
+        );
+    }
+
+    #[test]
+    fn test_fn_signature_for_macro_call() {
+        let info = call_info(
+            r#"
+/// Adds one to the number given.
+macro_rules! add_one {
+    ($x:expr) => { $x + 1 };
+}
+
+fn bar() {
+    add_one!(<|>);
+}"#,
+        );
+
+        assert_eq!(param_labels(&info), vec!("$x:expr".to_string()));
+        assert_eq!(info.label, "add_one!($x:expr)".to_string());
+        assert_eq!(
+            info.parameters[0].range,
+            TextRange::from_to(TextUnit::from_usize(9), TextUnit::from_usize(16))
+        );
+        assert_eq!(info.doc, Some("Adds one to the number given.".into()));
+    }
+
+    #[test]
+    fn test_fn_signature_for_macro_call_with_no_captures() {
+        let info = call_info(
+            r#"macro_rules! log {
+    () => { println!("logging") };
+}
+
+fn bar() {
+    log!(<|>);
+}"#,
+        );
+
+        assert_eq!(param_labels(&info), Vec::<String>::new());
+        assert_eq!(info.label, "log!()".to_string());
+    }
+
+    #[test]
+    fn test_fn_signature_for_macro_call_with_brace_matcher() {
+        // `{ ... }`-delimited matchers aren't understood by the paren-based
+        // matcher scan, so this falls back to a signature with no
+        // parameters rather than panicking or misparsing.
+        let info = call_info(
+            r#"macro_rules! add_one {
+    { $x:expr } => { $x + 1 };
+}
+
+fn bar() {
+    add_one!(<|>);
+}"#,
+        );
+
+        assert_eq!(param_labels(&info), Vec::<String>::new());
+        assert_eq!(info.label, "add_one!()".to_string());
+    }
+
+    #[test]
+    fn test_fn_signature_for_macro_call_with_non_ascii_before_matcher() {
+        // A multi-byte character in an earlier, non-paren-delimited arm
+        // used to desync the byte-offset `find('(')` from the char-count
+        // based scan in `find_matching_paren`, making it walk past the
+        // real `(` of the later arm entirely.
+        let info = call_info(
+            r#"macro_rules! greet {
+    { "héllo" } => { 0 };
+    ($x:expr) => { $x + 1 };
+}
+
+fn bar() {
+    greet!(<|>);
+}"#,
+        );
+
+        assert_eq!(param_labels(&info), vec!("$x:expr".to_string()));
+        assert_eq!(info.label, "greet!($x:expr)".to_string());
+    }
+
 }